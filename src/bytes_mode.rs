@@ -0,0 +1,182 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyIOError;
+use indexmap::IndexMap;
+use rayon::prelude::*;
+use regex::bytes::Regex as BytesRegex;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::sync::Arc;
+
+use crate::patterns::translate_pattern_bytes;
+
+/// Splits `content` into lines the same way `str::lines()` does — on `\n`,
+/// stripping a trailing `\r`, and without yielding a trailing empty line for
+/// input that ends in a newline — but over raw bytes, so it has no UTF-8
+/// requirement at all.
+fn split_lines(content: &[u8]) -> Vec<&[u8]> {
+    if content.is_empty() {
+        return Vec::new();
+    }
+    let content = content.strip_suffix(b"\n").unwrap_or(content);
+    content
+        .split(|&b| b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .collect()
+}
+
+/// Compiles a map of byte patterns to byte replacements into `regex::bytes::Regex`
+/// pairs. The byte counterpart of `compile_rules`, for logs that aren't
+/// guaranteed to be valid UTF-8 (latin-1 fragments, truncated multibyte
+/// sequences, embedded binary).
+///
+/// Patterns go through `translate_pattern_bytes`, not a lossy UTF-8
+/// conversion, so a genuine non-UTF-8 byte in a pattern key still compiles to
+/// a regex that matches that exact byte instead of silently matching U+FFFD.
+pub(crate) fn compile_rules_bytes(
+    rules: &IndexMap<Vec<u8>, Vec<u8>>,
+) -> PyResult<Vec<(BytesRegex, Vec<u8>)>> {
+    rules
+        .iter()
+        .map(|(pattern, replacement)| {
+            let source = translate_pattern_bytes(pattern);
+            BytesRegex::new(&source)
+                .map(|r| (r, replacement.clone()))
+                .map_err(|e| {
+                    PyIOError::new_err(format!(
+                        "Invalid regex pattern '{}': {}",
+                        String::from_utf8_lossy(pattern),
+                        e
+                    ))
+                })
+        })
+        .collect::<PyResult<Vec<_>>>()
+}
+
+pub(crate) fn scrub_line_bytes(line: &[u8], compiled_rules: &[(BytesRegex, Vec<u8>)]) -> Vec<u8> {
+    let mut result = line.to_vec();
+    for (pattern, replacement) in compiled_rules {
+        result = pattern.replace_all(&result, replacement.as_slice()).into_owned();
+    }
+    result
+}
+
+/// Scrubs one chunk's worth of lines, returning the scrubbed bytes (each
+/// line followed by `\n`) and how many lines it contained. The byte-oriented
+/// counterpart of `crate::mmap_chunks::scrub_chunk` — `chunk` is assumed to
+/// start and end on a line boundary, which `chunk_boundaries` guarantees for
+/// every chunk but possibly the last.
+fn scrub_chunk_bytes(chunk: &[u8], compiled_rules: &[(BytesRegex, Vec<u8>)]) -> (Vec<u8>, usize) {
+    let lines = split_lines(chunk);
+    let mut out = Vec::with_capacity(chunk.len());
+    for line in &lines {
+        out.extend_from_slice(&scrub_line_bytes(line, compiled_rules));
+        out.push(b'\n');
+    }
+    (out, lines.len())
+}
+
+/// Byte-oriented counterpart to [`crate::scrubber::Scrubber`] for logs that
+/// aren't guaranteed to be valid UTF-8. Patterns and replacements are raw
+/// bytes (Python `bytes`, not `str`), matching is done with
+/// `regex::bytes::Regex`, and files are split and rejoined on `\n` at the
+/// byte level rather than decoded.
+#[pyclass]
+pub struct BytesScrubber {
+    rules: Arc<Vec<(BytesRegex, Vec<u8>)>>,
+}
+
+#[pymethods]
+impl BytesScrubber {
+    #[new]
+    pub(crate) fn new(rules: IndexMap<Vec<u8>, Vec<u8>>) -> PyResult<Self> {
+        Ok(BytesScrubber {
+            rules: Arc::new(compile_rules_bytes(&rules)?),
+        })
+    }
+
+    /// Transforms a single byte string using the precompiled rules.
+    pub(crate) fn scrub_bytes(&self, data: Vec<u8>) -> Vec<u8> {
+        scrub_line_bytes(&data, &self.rules)
+    }
+
+    /// Processes a log file in parallel without requiring valid UTF-8,
+    /// writing scrubbed bytes straight through to `output_path`.
+    pub(crate) fn scrub_file(&self, input_path: String, output_path: String) -> PyResult<usize> {
+        let rules = Arc::clone(&self.rules);
+
+        let input_file = File::open(&input_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
+
+        let mut content = Vec::new();
+        BufReader::new(input_file)
+            .read_to_end(&mut content)
+            .map_err(|e| PyIOError::new_err(format!("Failed to read input file: {}", e)))?;
+
+        let lines = split_lines(&content);
+        let line_count = lines.len();
+
+        let scrubbed_lines: Vec<Vec<u8>> = lines
+            .par_iter()
+            .map(|line| scrub_line_bytes(line, &rules))
+            .collect();
+
+        let output_file = File::create(&output_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
+
+        let mut writer = BufWriter::new(output_file);
+        for line in scrubbed_lines {
+            writer.write_all(&line)
+                .and_then(|_| writer.write_all(b"\n"))
+                .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
+        }
+
+        writer.flush()
+            .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
+
+        Ok(line_count)
+    }
+
+    /// Processes a large log file via memory-mapped I/O without requiring
+    /// valid UTF-8.
+    ///
+    /// Scrubs the mapping in fixed-size, line-aligned chunks rather than
+    /// collecting every line (and every scrubbed line) into memory at once —
+    /// same bounded-memory design as `Scrubber::scrub_file_mmap`, via the
+    /// byte-level chunk boundaries shared with `crate::mmap_chunks`.
+    pub(crate) fn scrub_file_mmap(&self, input_path: String, output_path: String) -> PyResult<usize> {
+        let rules = Arc::clone(&self.rules);
+
+        let input_file = File::open(&input_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
+
+        let mmap = unsafe { memmap2::Mmap::map(&input_file) }
+            .map_err(|e| PyIOError::new_err(format!("Failed to memory-map input file: {}", e)))?;
+
+        let output_file = File::create(&output_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
+        let mut writer = BufWriter::new(output_file);
+
+        let boundaries = crate::mmap_chunks::chunk_boundaries(&mmap);
+        let batch_size = rayon::current_num_threads().max(1);
+
+        let mut total_lines = 0;
+        for batch in boundaries.chunks(batch_size) {
+            let scrubbed: Vec<(Vec<u8>, usize)> = batch
+                .par_iter()
+                .map(|&(start, end)| scrub_chunk_bytes(&mmap[start..end], &rules))
+                .collect();
+
+            for (bytes, count) in scrubbed {
+                writer
+                    .write_all(&bytes)
+                    .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
+                total_lines += count;
+            }
+        }
+
+        writer.flush()
+            .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
+
+        Ok(total_lines)
+    }
+}
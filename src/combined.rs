@@ -0,0 +1,99 @@
+use indexmap::IndexMap;
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+use regex::{Captures, Regex};
+
+use crate::patterns::translate_pattern;
+
+/// Every rule's pattern wrapped in its own named group and joined with `|`,
+/// so a line can be scrubbed in a single pass instead of one `replace_all`
+/// per rule. This is the `RegexSet` idea from the `regex` crate, applied to
+/// replacement: match once, then find out which rule matched.
+///
+/// Alternation in the `regex` crate is leftmost-first, so wrapping rules in
+/// `(?P<g0>...)|(?P<g1>...)|...` in the caller's insertion order means
+/// earlier rules win ties exactly as the sequential per-rule engine does.
+/// Named (rather than numbered) groups are required: numbering a user
+/// pattern's own capture groups would shift once it's wrapped alongside
+/// others, so `$1`-style back-references are not supported in combined mode.
+///
+/// `regex` is `None` for an empty rules map: there's no pattern to alternate
+/// over (`Regex::new("")` would compile but match the empty string
+/// everywhere, with no outer group to resolve), so `scrub_line`/
+/// `scrub_line_with_stats` short-circuit to a no-op instead, matching the
+/// `PerRule` path's behavior for the same empty-rules input.
+pub(crate) struct CombinedRules {
+    regex: Option<Regex>,
+    group_names: Vec<String>,
+    replacements: Vec<String>,
+}
+
+impl CombinedRules {
+    pub(crate) fn compile(rules: &IndexMap<String, String>) -> PyResult<Self> {
+        if rules.is_empty() {
+            return Ok(CombinedRules {
+                regex: None,
+                group_names: Vec::new(),
+                replacements: Vec::new(),
+            });
+        }
+
+        let mut source = String::new();
+        let mut group_names = Vec::with_capacity(rules.len());
+        let mut replacements = Vec::with_capacity(rules.len());
+
+        for (i, (pattern, replacement)) in rules.iter().enumerate() {
+            if i > 0 {
+                source.push('|');
+            }
+            let group_name = format!("g{}", i);
+            source.push_str(&format!("(?P<{}>{})", group_name, translate_pattern(pattern)));
+            group_names.push(group_name);
+            replacements.push(replacement.clone());
+        }
+
+        let regex = Regex::new(&source)
+            .map_err(|e| PyIOError::new_err(format!("Invalid combined regex: {}", e)))?;
+
+        Ok(CombinedRules {
+            regex: Some(regex),
+            group_names,
+            replacements,
+        })
+    }
+
+    /// Which rule fired for this match, found by checking which outer named
+    /// group is the one that captured.
+    fn matched_rule(&self, caps: &Captures) -> usize {
+        self.group_names
+            .iter()
+            .position(|name| caps.name(name).is_some())
+            .expect("combined regex matched but no outer group captured")
+    }
+
+    pub(crate) fn scrub_line(&self, line: &str) -> String {
+        let Some(regex) = &self.regex else {
+            return line.to_string();
+        };
+        regex
+            .replace_all(line, |caps: &Captures| {
+                self.replacements[self.matched_rule(caps)].clone()
+            })
+            .into_owned()
+    }
+
+    /// Same as `scrub_line`, but tallies each rule's match count (indexed the
+    /// same as the rules were defined) into `counts` as it resolves matches.
+    pub(crate) fn scrub_line_with_stats(&self, line: &str, counts: &mut [usize]) -> String {
+        let Some(regex) = &self.regex else {
+            return line.to_string();
+        };
+        regex
+            .replace_all(line, |caps: &Captures| {
+                let rule = self.matched_rule(caps);
+                counts[rule] += 1;
+                self.replacements[rule].clone()
+            })
+            .into_owned()
+    }
+}
@@ -0,0 +1,107 @@
+use indexmap::IndexMap;
+use pyo3::PyResult;
+use regex::{Captures, Regex};
+use std::sync::Arc;
+
+use crate::combined::CombinedRules;
+use crate::{compile_rules, scrub_line};
+
+/// Which matching strategy a scrubbing session was built with.
+///
+/// `PerRule` runs each rule's `replace_all` in sequence, same as the original
+/// free functions. `Combined` joins every pattern into one alternation regex
+/// and resolves all rules in a single pass per line; see [`CombinedRules`]
+/// for why that needs insertion-ordered rules.
+enum Strategy {
+    PerRule(Arc<Vec<(Regex, String)>>),
+    Combined(Arc<CombinedRules>),
+}
+
+/// A compiled set of rules plus the original rule keys (pattern text as the
+/// caller wrote it, before `glob:`/`literal:`/`re:` translation), in the same
+/// order the rules were defined. The keys are what per-rule match statistics
+/// are reported against.
+///
+/// Shared by [`crate::scrubber::Scrubber`] and the follow/tail streaming mode
+/// so both pick up either matching strategy the same way.
+pub(crate) struct Engine {
+    strategy: Strategy,
+    rule_keys: Arc<Vec<String>>,
+}
+
+impl Engine {
+    pub(crate) fn compile(rules: &IndexMap<String, String>, combined: bool) -> PyResult<Self> {
+        let rule_keys = Arc::new(rules.keys().cloned().collect());
+        let strategy = if combined {
+            Strategy::Combined(Arc::new(CombinedRules::compile(rules)?))
+        } else {
+            Strategy::PerRule(Arc::new(compile_rules(rules)?))
+        };
+        Ok(Engine { strategy, rule_keys })
+    }
+
+    pub(crate) fn scrub(&self, line: &str) -> String {
+        match &self.strategy {
+            Strategy::PerRule(rules) => scrub_line(line, rules),
+            Strategy::Combined(rules) => rules.scrub_line(line),
+        }
+    }
+
+    /// Same as `scrub`, but adds each rule's match count (indexed the same
+    /// as `rule_keys`) into `counts`, which the caller reduces across lines
+    /// (and, for parallel callers, across Rayon workers).
+    pub(crate) fn scrub_with_stats(&self, line: &str, counts: &mut [usize]) -> String {
+        match &self.strategy {
+            Strategy::PerRule(rules) => scrub_line_with_stats(line, rules, counts),
+            Strategy::Combined(rules) => rules.scrub_line_with_stats(line, counts),
+        }
+    }
+
+    pub(crate) fn rule_count(&self) -> usize {
+        self.rule_keys.len()
+    }
+
+    pub(crate) fn rule_keys(&self) -> &[String] {
+        &self.rule_keys
+    }
+
+    pub(crate) fn cloned(&self) -> Engine {
+        Engine {
+            strategy: match &self.strategy {
+                Strategy::PerRule(rules) => Strategy::PerRule(Arc::clone(rules)),
+                Strategy::Combined(rules) => Strategy::Combined(Arc::clone(rules)),
+            },
+            rule_keys: Arc::clone(&self.rule_keys),
+        }
+    }
+}
+
+/// Per-rule-statistics counterpart to `scrub_line`: applies each rule in
+/// order, same as `scrub_line`, but tallies how many replacements each rule
+/// made via the `Replacer` closure passed to `replace_all`, so counting and
+/// replacing happen in the same pass.
+///
+/// The closure expands `replacement` via `Captures::expand` rather than
+/// returning it verbatim, so `$1`/`$name` back-references behave exactly as
+/// they do for the `&str` `Replacer` impl `scrub_line` relies on.
+fn scrub_line_with_stats(
+    line: &str,
+    compiled_rules: &[(Regex, String)],
+    counts: &mut [usize],
+) -> String {
+    let mut result = line.to_string();
+    let mut expanded = String::new();
+    for (i, (pattern, replacement)) in compiled_rules.iter().enumerate() {
+        let mut matches = 0;
+        result = pattern
+            .replace_all(&result, |caps: &Captures| {
+                matches += 1;
+                expanded.clear();
+                caps.expand(replacement, &mut expanded);
+                expanded.clone()
+            })
+            .into_owned();
+        counts[i] += matches;
+    }
+    result
+}
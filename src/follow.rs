@@ -0,0 +1,274 @@
+use pyo3::exceptions::{PyIOError, PyStopIteration};
+use pyo3::prelude::*;
+use indexmap::IndexMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::engine::Engine;
+
+/// The file's inode, used to tell a rotated file (replaced under the same
+/// path) apart from one that's merely grown. `None` on platforms without the
+/// concept, in which case rotation is only caught via a length shrink.
+#[cfg(unix)]
+fn inode_of(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn inode_of(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+enum Source {
+    /// Tails a named file, reopening it when it's rotated or truncated.
+    File {
+        path: String,
+        reader: BufReader<File>,
+        inode: Option<u64>,
+    },
+    /// Reads from stdin via a dedicated background thread, which has no
+    /// rotation/truncation to worry about.
+    ///
+    /// A blocking `read_line` on real stdin has no way to time out, so it's
+    /// done on its own thread and handed to `next_scrubbed` over this
+    /// channel. `recv_timeout` lets the poll loop keep re-checking
+    /// `stop_flag` at `poll_interval_ms` even while stdin itself is still
+    /// parked waiting for input — the background thread may outlive the
+    /// `Follower` until stdin next produces data or closes, but that's a
+    /// harmless orphaned thread, not a hang visible to Python.
+    Stdin(mpsc::Receiver<io::Result<Option<String>>>),
+}
+
+/// Reads one line, stripping the trailing `\n`/`\r\n`. Blocks until a line is
+/// available or the underlying reader reports EOF.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut buf = String::new();
+    if reader.read_line(&mut buf)? == 0 {
+        return Ok(None);
+    }
+    if buf.ends_with('\n') {
+        buf.pop();
+        if buf.ends_with('\r') {
+            buf.pop();
+        }
+    }
+    Ok(Some(buf))
+}
+
+/// Spawns the background thread that feeds a `Source::Stdin` channel, one
+/// `read_line` result at a time, stopping after the first `Ok(None)` (EOF)
+/// or `Err`.
+fn spawn_stdin_reader() -> mpsc::Receiver<io::Result<Option<String>>> {
+    let (tx, rx) = mpsc::sync_channel(0);
+    thread::spawn(move || {
+        let mut reader = BufReader::new(io::stdin());
+        loop {
+            let line = read_line(&mut reader);
+            let done = !matches!(line, Ok(Some(_)));
+            if tx.send(line).is_err() || done {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// What one poll of the source produced.
+enum Polled {
+    /// A line is ready to be scrubbed.
+    Line(String),
+    /// No line yet, but the source is still live — keep polling.
+    Pending,
+    /// The source is exhausted for good (stdin closed).
+    Ended,
+}
+
+/// Streams newly-appended lines from a growing log file (or stdin), scrubbing
+/// each one as it arrives — the `tail -f | scrub` pattern. Iterate it directly
+/// from Python (`for line in follower: ...`) to get scrubbed lines as a
+/// generator, or call `run_to_sink` to have it write them straight to an
+/// output file with a flush after every line.
+///
+/// Rotation and truncation are handled by reopening the source path: a
+/// changed inode means the file was rotated (e.g. by logrotate) and a
+/// shrunk length means it was truncated in place. Call `stop()` from another
+/// Python thread to end the loop cleanly; the blocking wait for new data
+/// releases the GIL so that thread can actually run. File sources notice
+/// `stop()` within one `poll_interval_ms`; stdin sources do too, since the
+/// actual blocking stdin read happens on a background thread and the poll
+/// loop only ever waits on it with a `poll_interval_ms` timeout.
+#[pyclass]
+pub struct Follower {
+    source: Source,
+    engine: Engine,
+    stop_flag: Arc<AtomicBool>,
+    poll_interval_ms: u64,
+}
+
+impl Follower {
+    /// Reopens the source file if it was rotated or truncated since the last
+    /// read. No-op for stdin.
+    fn reopen_if_needed(&mut self) -> PyResult<()> {
+        let Source::File { path, reader, inode } = &mut self.source else {
+            return Ok(());
+        };
+
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            // The file may be momentarily missing mid-rotation; try again next poll.
+            return Ok(());
+        };
+
+        let rotated = inode_of(&metadata) != *inode;
+        let current_pos = reader.stream_position().unwrap_or(0);
+        let truncated = metadata.len() < current_pos;
+
+        if rotated || truncated {
+            let file = File::open(&path)
+                .map_err(|e| PyIOError::new_err(format!("Failed to reopen '{}': {}", path, e)))?;
+            *inode = inode_of(
+                &file
+                    .metadata()
+                    .map_err(|e| PyIOError::new_err(format!("Failed to stat '{}': {}", path, e)))?,
+            );
+            *reader = BufReader::new(file);
+        }
+
+        Ok(())
+    }
+
+    /// One poll of the current source. For a file, a `None` from `read_line`
+    /// just means "nothing new yet" — the file might still grow. For stdin,
+    /// `recv_timeout` distinguishes that same "nothing yet" case (tried
+    /// again next loop) from the channel actually closing (stdin hit real
+    /// EOF), which ends the stream for good.
+    fn poll_source(&mut self) -> PyResult<Polled> {
+        match &mut self.source {
+            Source::File { reader, .. } => read_line(reader)
+                .map_err(|e| PyIOError::new_err(format!("Failed to read next line: {}", e)))
+                .map(|line| match line {
+                    Some(line) => Polled::Line(line),
+                    None => Polled::Pending,
+                }),
+            Source::Stdin(rx) => match rx.recv_timeout(Duration::from_millis(self.poll_interval_ms)) {
+                Ok(Ok(Some(line))) => Ok(Polled::Line(line)),
+                Ok(Ok(None)) => Ok(Polled::Ended),
+                Ok(Err(e)) => Err(PyIOError::new_err(format!("Failed to read next line: {}", e))),
+                Err(mpsc::RecvTimeoutError::Timeout) => Ok(Polled::Pending),
+                Err(mpsc::RecvTimeoutError::Disconnected) => Ok(Polled::Ended),
+            },
+        }
+    }
+
+    /// Blocks (polling at `poll_interval_ms`) until a scrubbed line is ready
+    /// or `stop()` has been called or the source is exhausted, in which case
+    /// it returns `None`.
+    fn next_scrubbed(&mut self) -> PyResult<Option<String>> {
+        loop {
+            if self.stop_flag.load(Ordering::Relaxed) {
+                return Ok(None);
+            }
+
+            match self.poll_source()? {
+                Polled::Line(line) => return Ok(Some(self.engine.scrub(&line))),
+                Polled::Ended => return Ok(None),
+                Polled::Pending => {
+                    self.reopen_if_needed()?;
+                    // Stdin's `recv_timeout` above already waited
+                    // `poll_interval_ms`; only a file poll (an instant,
+                    // non-blocking `read_line` that found nothing) needs an
+                    // explicit sleep before trying again.
+                    if matches!(self.source, Source::File { .. }) {
+                        thread::sleep(Duration::from_millis(self.poll_interval_ms));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Follower {
+    /// Opens `path` for following, or stdin if `path` is `None`. Starts
+    /// reading from the end of an existing file, matching `tail -f`.
+    #[new]
+    #[pyo3(signature = (path, rules, combined=false, poll_interval_ms=200))]
+    fn new(
+        path: Option<String>,
+        rules: IndexMap<String, String>,
+        combined: bool,
+        poll_interval_ms: u64,
+    ) -> PyResult<Self> {
+        let engine = Engine::compile(&rules, combined)?;
+
+        let source = match path {
+            Some(path) => {
+                let mut file = File::open(&path)
+                    .map_err(|e| PyIOError::new_err(format!("Failed to open '{}': {}", path, e)))?;
+                file.seek(SeekFrom::End(0))
+                    .map_err(|e| PyIOError::new_err(format!("Failed to seek '{}': {}", path, e)))?;
+
+                let inode = inode_of(
+                    &file
+                        .metadata()
+                        .map_err(|e| PyIOError::new_err(format!("Failed to stat '{}': {}", path, e)))?,
+                );
+
+                Source::File {
+                    path,
+                    reader: BufReader::new(file),
+                    inode,
+                }
+            }
+            None => Source::Stdin(spawn_stdin_reader()),
+        };
+
+        Ok(Follower {
+            source,
+            engine,
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            poll_interval_ms,
+        })
+    }
+
+    /// Signals the follow loop to stop at its next poll. Safe to call from a
+    /// different Python thread than the one iterating.
+    fn stop(&self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Writes each scrubbed line to `output_path` as it arrives, flushing
+    /// after every line so downstream consumers see it immediately. Runs
+    /// until `stop()` is called or, for stdin, the input is closed.
+    fn run_to_sink(&mut self, py: Python<'_>, output_path: String) -> PyResult<()> {
+        let mut output = File::create(&output_path).map_err(|e| {
+            PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e))
+        })?;
+
+        loop {
+            let next = py.allow_threads(|| self.next_scrubbed())?;
+            let Some(line) = next else {
+                return Ok(());
+            };
+            writeln!(output, "{}", line)
+                .and_then(|_| output.flush())
+                .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
+        }
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<String> {
+        match py.allow_threads(|| self.next_scrubbed())? {
+            Some(line) => Ok(line),
+            None => Err(PyStopIteration::new_err(())),
+        }
+    }
+}
@@ -1,11 +1,47 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::PyIOError;
-use rayon::prelude::*;
+use indexmap::IndexMap;
 use regex::Regex;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::sync::Arc;
+
+mod bytes_mode;
+mod combined;
+mod engine;
+mod follow;
+mod mmap_chunks;
+mod patterns;
+mod scrubber;
+
+use bytes_mode::BytesScrubber;
+use follow::Follower;
+use patterns::translate_pattern;
+use scrubber::Scrubber;
+
+/// Compiles an `IndexMap<String, String>` of pattern -> replacement pairs into
+/// `(Regex, String)` pairs, ready to be passed to `scrub_line`.
+///
+/// Rules keep the order the caller defined them in (an `IndexMap`, not a
+/// `HashMap`, so Python's dict insertion order survives the FFI boundary) —
+/// the combined alternation engine in [`combined`] relies on that order to
+/// resolve which rule wins when two patterns could match the same text.
+///
+/// Each key is first run through [`translate_pattern`], so `glob:`, `literal:`,
+/// and `re:` prefixes are resolved to their final regex source before
+/// compilation.
+///
+/// Shared by every text entry point that uses the per-rule engine, so rule
+/// compilation and its error message stay in one place. The combined engine
+/// and the byte-oriented mode compile their rules separately.
+fn compile_rules(rules: &IndexMap<String, String>) -> PyResult<Vec<(Regex, String)>> {
+    rules
+        .iter()
+        .map(|(pattern, replacement)| {
+            let source = translate_pattern(pattern);
+            Regex::new(&source)
+                .map(|r| (r, replacement.clone()))
+                .map_err(|e| PyIOError::new_err(format!("Invalid regex pattern '{}': {}", pattern, e)))
+        })
+        .collect::<PyResult<Vec<_>>>()
+}
 
 /// Applies regex-based pattern matching and replacement to a single line of text.
 ///
@@ -20,7 +56,7 @@ use std::sync::Arc;
 /// # Returns
 ///
 /// The transformed text with all matching patterns replaced
-fn scrub_line(line: &str, compiled_rules: &[(Regex, String)]) -> String {
+pub(crate) fn scrub_line(line: &str, compiled_rules: &[(Regex, String)]) -> String {
     let mut result = line.to_string();
     for (pattern, replacement) in compiled_rules {
         result = pattern.replace_all(&result, replacement.as_str()).to_string();
@@ -76,53 +112,9 @@ fn scrub_line(line: &str, compiled_rules: &[(Regex, String)]) -> String {
 fn scrub_logs_parallel(
     input_path: String,
     output_path: String,
-    rules: HashMap<String, String>,
+    rules: IndexMap<String, String>,
 ) -> PyResult<usize> {
-    // Compile all regex patterns upfront
-    let compiled_rules: Vec<(Regex, String)> = rules
-        .iter()
-        .map(|(pattern, replacement)| {
-            Regex::new(pattern)
-                .map(|r| (r, replacement.clone()))
-                .map_err(|e| PyIOError::new_err(format!("Invalid regex pattern '{}': {}", pattern, e)))
-        })
-        .collect::<PyResult<Vec<_>>>()?;
-
-    // Wrap in Arc for thread-safe sharing
-    let compiled_rules = Arc::new(compiled_rules);
-
-    // Read all lines from input file
-    let input_file = File::open(&input_path)
-        .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
-    
-    let reader = BufReader::new(input_file);
-    let lines: Vec<String> = reader
-        .lines()
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| PyIOError::new_err(format!("Failed to read input file: {}", e)))?;
-
-    let line_count = lines.len();
-
-    // Process lines in parallel using rayon
-    let scrubbed_lines: Vec<String> = lines
-        .par_iter()
-        .map(|line| scrub_line(line, &compiled_rules))
-        .collect();
-
-    // Write scrubbed lines to output file
-    let output_file = File::create(&output_path)
-        .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
-    
-    let mut writer = BufWriter::new(output_file);
-    for line in scrubbed_lines {
-        writeln!(writer, "{}", line)
-            .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
-    }
-
-    writer.flush()
-        .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
-
-    Ok(line_count)
+    Scrubber::new(rules, false)?.scrub_file(input_path, output_path)
 }
 
 /// Processes large log files using memory-mapped I/O for maximum performance.
@@ -151,54 +143,9 @@ fn scrub_logs_parallel(
 fn scrub_logs_mmap(
     input_path: String,
     output_path: String,
-    rules: HashMap<String, String>,
+    rules: IndexMap<String, String>,
 ) -> PyResult<usize> {
-    // Compile all regex patterns upfront
-    let compiled_rules: Vec<(Regex, String)> = rules
-        .iter()
-        .map(|(pattern, replacement)| {
-            Regex::new(pattern)
-                .map(|r| (r, replacement.clone()))
-                .map_err(|e| PyIOError::new_err(format!("Invalid regex pattern '{}': {}", pattern, e)))
-        })
-        .collect::<PyResult<Vec<_>>>()?;
-
-    let compiled_rules = Arc::new(compiled_rules);
-
-    // Memory-map the input file
-    let input_file = File::open(&input_path)
-        .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
-    
-    let mmap = unsafe { memmap2::Mmap::map(&input_file) }
-        .map_err(|e| PyIOError::new_err(format!("Failed to memory-map input file: {}", e)))?;
-
-    // Convert to string and split into lines
-    let content = std::str::from_utf8(&mmap)
-        .map_err(|e| PyIOError::new_err(format!("Invalid UTF-8 in input file: {}", e)))?;
-    
-    let lines: Vec<&str> = content.lines().collect();
-    let line_count = lines.len();
-
-    // Process lines in parallel
-    let scrubbed_lines: Vec<String> = lines
-        .par_iter()
-        .map(|line| scrub_line(line, &compiled_rules))
-        .collect();
-
-    // Write scrubbed lines to output file
-    let output_file = File::create(&output_path)
-        .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
-    
-    let mut writer = BufWriter::new(output_file);
-    for line in scrubbed_lines {
-        writeln!(writer, "{}", line)
-            .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
-    }
-
-    writer.flush()
-        .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
-
-    Ok(line_count)
+    Scrubber::new(rules, false)?.scrub_file_mmap(input_path, output_path)
 }
 
 /// Transforms a single string using regex pattern matching.
@@ -238,17 +185,68 @@ fn scrub_logs_mmap(
 /// # Returns: 'User: [EMAIL] password=[REDACTED]'
 /// ```
 #[pyfunction]
-fn scrub_text(text: String, rules: HashMap<String, String>) -> PyResult<String> {
-    let compiled_rules: Vec<(Regex, String)> = rules
-        .iter()
-        .map(|(pattern, replacement)| {
-            Regex::new(pattern)
-                .map(|r| (r, replacement.clone()))
-                .map_err(|e| PyIOError::new_err(format!("Invalid regex pattern '{}': {}", pattern, e)))
-        })
-        .collect::<PyResult<Vec<_>>>()?;
+fn scrub_text(text: String, rules: IndexMap<String, String>) -> PyResult<String> {
+    Ok(Scrubber::new(rules, false)?.scrub_text(text))
+}
 
-    Ok(scrub_line(&text, &compiled_rules))
+/// Same as `scrub_text`, but also returns a per-rule match count — original
+/// rule key to number of replacements — for compliance reporting.
+#[pyfunction]
+fn scrub_text_with_stats(
+    text: String,
+    rules: IndexMap<String, String>,
+) -> PyResult<(String, IndexMap<String, usize>)> {
+    Ok(Scrubber::new(rules, false)?.scrub_text_with_stats(text))
+}
+
+/// Same as `scrub_logs_parallel`, but also returns a per-rule match count.
+#[pyfunction]
+fn scrub_logs_parallel_with_stats(
+    input_path: String,
+    output_path: String,
+    rules: IndexMap<String, String>,
+) -> PyResult<(usize, IndexMap<String, usize>)> {
+    Scrubber::new(rules, false)?.scrub_file_with_stats(input_path, output_path)
+}
+
+/// Same as `scrub_logs_mmap`, but also returns a per-rule match count.
+#[pyfunction]
+fn scrub_logs_mmap_with_stats(
+    input_path: String,
+    output_path: String,
+    rules: IndexMap<String, String>,
+) -> PyResult<(usize, IndexMap<String, usize>)> {
+    Scrubber::new(rules, false)?.scrub_file_mmap_with_stats(input_path, output_path)
+}
+
+/// Byte-oriented counterpart to `scrub_logs_parallel` for logs that aren't
+/// guaranteed to be valid UTF-8. `rules` maps raw byte patterns to raw byte
+/// replacements (Python `bytes`, not `str`).
+#[pyfunction]
+fn scrub_logs_bytes_parallel(
+    input_path: String,
+    output_path: String,
+    rules: IndexMap<Vec<u8>, Vec<u8>>,
+) -> PyResult<usize> {
+    BytesScrubber::new(rules)?.scrub_file(input_path, output_path)
+}
+
+/// Byte-oriented counterpart to `scrub_logs_mmap` for logs that aren't
+/// guaranteed to be valid UTF-8.
+#[pyfunction]
+fn scrub_logs_bytes_mmap(
+    input_path: String,
+    output_path: String,
+    rules: IndexMap<Vec<u8>, Vec<u8>>,
+) -> PyResult<usize> {
+    BytesScrubber::new(rules)?.scrub_file_mmap(input_path, output_path)
+}
+
+/// Byte-oriented counterpart to `scrub_text` for data that isn't guaranteed
+/// to be valid UTF-8.
+#[pyfunction]
+fn scrub_bytes(data: Vec<u8>, rules: IndexMap<Vec<u8>, Vec<u8>>) -> PyResult<Vec<u8>> {
+    Ok(BytesScrubber::new(rules)?.scrub_bytes(data))
 }
 
 /// Sentinel-RS: Production-grade pattern matching engine for Python.
@@ -288,11 +286,21 @@ fn scrub_text(text: String, rules: HashMap<String, String>) -> PyResult<String>
 /// - 10-50x faster than pure Python implementations
 /// - Process 100K-1M+ lines per second (depending on pattern complexity)
 /// - Linear scaling with CPU core count
-/// - Memory footprint: ~2-3x input file size (buffered) or ~1x (memory-mapped)
+/// - Memory footprint: ~2-3x input file size (buffered) or a handful of
+///   chunks' worth, independent of input size (memory-mapped)
 #[pymodule]
 fn sentinel_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(scrub_logs_parallel, m)?)?;
     m.add_function(wrap_pyfunction!(scrub_logs_mmap, m)?)?;
     m.add_function(wrap_pyfunction!(scrub_text, m)?)?;
+    m.add_function(wrap_pyfunction!(scrub_text_with_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(scrub_logs_parallel_with_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(scrub_logs_mmap_with_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(scrub_logs_bytes_parallel, m)?)?;
+    m.add_function(wrap_pyfunction!(scrub_logs_bytes_mmap, m)?)?;
+    m.add_function(wrap_pyfunction!(scrub_bytes, m)?)?;
+    m.add_class::<Scrubber>()?;
+    m.add_class::<BytesScrubber>()?;
+    m.add_class::<Follower>()?;
     Ok(())
 }
@@ -0,0 +1,164 @@
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+use rayon::prelude::*;
+use std::io::Write;
+
+use crate::engine::Engine;
+
+/// Target size for one chunk before it gets snapped to the next line
+/// boundary. Keeps any single chunk's scrubbed output, plus the handful of
+/// chunks a batch holds at once, well short of the whole file.
+const CHUNK_BYTES: usize = 8 * 1024 * 1024;
+
+/// Splits `content` into roughly `CHUNK_BYTES`-sized byte ranges, each
+/// snapped forward to the next `\n` so no chunk ever splits a line.
+///
+/// Pure byte-level slicing with no UTF-8 assumptions, so it's shared by both
+/// the text chunking below and `bytes_mode`'s chunked mmap path.
+pub(crate) fn chunk_boundaries(content: &[u8]) -> Vec<(usize, usize)> {
+    let len = content.len();
+    if len == 0 {
+        return Vec::new();
+    }
+
+    let mut boundaries = Vec::new();
+    let mut start = 0;
+    while start < len {
+        let target_end = (start + CHUNK_BYTES).min(len);
+        let end = if target_end >= len {
+            len
+        } else {
+            match content[target_end..].iter().position(|&b| b == b'\n') {
+                Some(offset) => target_end + offset + 1,
+                None => len,
+            }
+        };
+        boundaries.push((start, end));
+        start = end;
+    }
+    boundaries
+}
+
+/// Scrubs one chunk's worth of lines, returning the scrubbed bytes (each
+/// line followed by `\n`) and how many lines it contained.
+///
+/// `text` is assumed to start and end on a line boundary, which
+/// `chunk_boundaries` guarantees for every chunk but possibly the last.
+fn scrub_chunk(text: &str, engine: &Engine) -> (Vec<u8>, usize) {
+    let mut out = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut lines = text.split('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        if lines.peek().is_none() && line.is_empty() {
+            break;
+        }
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        out.push_str(&engine.scrub(line));
+        out.push('\n');
+        count += 1;
+    }
+
+    (out.into_bytes(), count)
+}
+
+/// Scrubs one chunk's worth of lines like `scrub_chunk`, but also tallies
+/// each rule's match count (indexed the same as `Engine::rule_keys`).
+fn scrub_chunk_with_stats(text: &str, engine: &Engine) -> (Vec<u8>, usize, Vec<usize>) {
+    let mut out = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut counts = vec![0usize; engine.rule_count()];
+    let mut lines = text.split('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        if lines.peek().is_none() && line.is_empty() {
+            break;
+        }
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        out.push_str(&engine.scrub_with_stats(line, &mut counts));
+        out.push('\n');
+        count += 1;
+    }
+
+    (out.into_bytes(), count, counts)
+}
+
+/// Scrubs a memory-mapped file in fixed-size, line-aligned chunks instead of
+/// materializing every line (and every scrubbed line) up front. Chunks are
+/// scrubbed `rayon::current_num_threads()` at a time and written to `writer`
+/// as each batch finishes, so only a handful of chunks are ever resident in
+/// memory regardless of how large the input is.
+pub(crate) fn scrub_mmap_chunked(
+    engine: &Engine,
+    mmap: &[u8],
+    writer: &mut impl Write,
+) -> PyResult<usize> {
+    std::str::from_utf8(mmap)
+        .map_err(|e| PyIOError::new_err(format!("Invalid UTF-8 in input file: {}", e)))?;
+
+    let boundaries = chunk_boundaries(mmap);
+    let batch_size = rayon::current_num_threads().max(1);
+
+    let mut total_lines = 0;
+    for batch in boundaries.chunks(batch_size) {
+        let scrubbed: Vec<(Vec<u8>, usize)> = batch
+            .par_iter()
+            .map(|&(start, end)| {
+                // Valid per the UTF-8 check above: chunk boundaries only ever
+                // fall right after a '\n', which can't appear inside a
+                // multi-byte UTF-8 sequence.
+                let text = std::str::from_utf8(&mmap[start..end])
+                    .expect("chunk boundaries are snapped to '\\n' in an already-validated UTF-8 file");
+                scrub_chunk(text, engine)
+            })
+            .collect();
+
+        for (bytes, count) in scrubbed {
+            writer
+                .write_all(&bytes)
+                .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
+            total_lines += count;
+        }
+    }
+
+    Ok(total_lines)
+}
+
+/// Same as `scrub_mmap_chunked`, but also returns each rule's total match
+/// count (indexed the same as `Engine::rule_keys`) for compliance reporting.
+pub(crate) fn scrub_mmap_chunked_with_stats(
+    engine: &Engine,
+    mmap: &[u8],
+    writer: &mut impl Write,
+) -> PyResult<(usize, Vec<usize>)> {
+    std::str::from_utf8(mmap)
+        .map_err(|e| PyIOError::new_err(format!("Invalid UTF-8 in input file: {}", e)))?;
+
+    let boundaries = chunk_boundaries(mmap);
+    let batch_size = rayon::current_num_threads().max(1);
+
+    let mut total_lines = 0;
+    let mut total_counts = vec![0usize; engine.rule_count()];
+    for batch in boundaries.chunks(batch_size) {
+        let scrubbed: Vec<(Vec<u8>, usize, Vec<usize>)> = batch
+            .par_iter()
+            .map(|&(start, end)| {
+                let text = std::str::from_utf8(&mmap[start..end])
+                    .expect("chunk boundaries are snapped to '\\n' in an already-validated UTF-8 file");
+                scrub_chunk_with_stats(text, engine)
+            })
+            .collect();
+
+        for (bytes, count, counts) in scrubbed {
+            writer
+                .write_all(&bytes)
+                .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
+            total_lines += count;
+            for (total, chunk_count) in total_counts.iter_mut().zip(counts) {
+                *total += chunk_count;
+            }
+        }
+    }
+
+    Ok((total_lines, total_counts))
+}
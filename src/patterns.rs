@@ -0,0 +1,167 @@
+/// Translates a rule key into the regex source that should actually be
+/// compiled, per Mercurial-style pattern-syntax prefixes:
+///
+/// - `re:PATTERN` — used verbatim, already a regex.
+/// - `literal:TEXT` — matched exactly, with every regex-special byte escaped.
+/// - `glob:PATTERN` — `*` becomes `.*`, `?` becomes a single-char wildcard,
+///   and everything else is escaped.
+/// - no recognized prefix — treated as `re:` for backward compatibility with
+///   callers that already hand-write regex.
+///
+/// Shared by every text-based entry point (plain and combined modes) so rule
+/// authoring stays consistent no matter which engine compiles the result. See
+/// `translate_pattern_bytes` for the byte-oriented counterpart used by
+/// `BytesScrubber`.
+pub(crate) fn translate_pattern(key: &str) -> String {
+    if let Some(pattern) = key.strip_prefix("re:") {
+        pattern.to_string()
+    } else if let Some(literal) = key.strip_prefix("literal:") {
+        regex::escape(literal)
+    } else if let Some(glob) = key.strip_prefix("glob:") {
+        translate_glob(glob)
+    } else {
+        key.to_string()
+    }
+}
+
+/// Translates glob syntax (`*`, `?`, literal runs) into regex source.
+fn translate_glob(glob: &str) -> String {
+    let mut out = String::with_capacity(glob.len() * 2);
+    let mut literal_run = String::new();
+
+    let flush = |literal_run: &mut String, out: &mut String| {
+        if !literal_run.is_empty() {
+            out.push_str(&regex::escape(literal_run));
+            literal_run.clear();
+        }
+    };
+
+    for c in glob.chars() {
+        match c {
+            '*' => {
+                flush(&mut literal_run, &mut out);
+                out.push_str(".*");
+            }
+            '?' => {
+                flush(&mut literal_run, &mut out);
+                out.push('.');
+            }
+            _ => literal_run.push(c),
+        }
+    }
+    flush(&mut literal_run, &mut out);
+
+    out
+}
+
+/// Byte-oriented counterpart to `translate_pattern`, for rule keys that
+/// aren't guaranteed to be valid UTF-8 (`BytesScrubber`'s patterns).
+///
+/// Any byte sequence that *is* valid UTF-8 is translated exactly like the
+/// `str` version, preserving regex syntax and unicode character classes the
+/// caller wrote. Bytes that aren't part of a valid UTF-8 sequence can't be
+/// written into a Rust `&str` at all, so they're hex-escaped (`(?-u:\xHH)`)
+/// instead — the `regex` crate parses that as a literal byte — rather than being
+/// lossily replaced, which would silently change which bytes the compiled
+/// pattern actually matches.
+pub(crate) fn translate_pattern_bytes(key: &[u8]) -> String {
+    if let Some(pattern) = key.strip_prefix(b"re:") {
+        bytes_to_source(pattern)
+    } else if let Some(literal) = key.strip_prefix(b"literal:") {
+        escape_bytes(literal)
+    } else if let Some(glob) = key.strip_prefix(b"glob:") {
+        translate_glob_bytes(glob)
+    } else {
+        bytes_to_source(key)
+    }
+}
+
+/// Passes valid UTF-8 through untouched (as regex source, preserving
+/// whatever syntax the caller wrote), hex-escaping only the byte runs that
+/// aren't part of a valid UTF-8 sequence.
+///
+/// Validating the pattern as one all-or-nothing unit would mean a single
+/// stray byte anywhere turns the *entire* pattern into a literal byte match
+/// — e.g. one bad byte next to `\d+` would escape `\`, `d`, and `+`
+/// literally instead of leaving `\d+` meaning "one or more digits". Walking
+/// valid/invalid runs keeps every valid-UTF-8 stretch of the pattern's regex
+/// semantics intact.
+fn bytes_to_source(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    let mut rest = bytes;
+
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                out.push_str(valid);
+                break;
+            }
+            Err(e) => {
+                let valid_len = e.valid_up_to();
+                out.push_str(
+                    std::str::from_utf8(&rest[..valid_len])
+                        .expect("valid_up_to bytes are valid UTF-8 by definition"),
+                );
+
+                let invalid_len = e.error_len().unwrap_or(rest.len() - valid_len);
+                out.push_str(&escape_bytes(&rest[valid_len..valid_len + invalid_len]));
+
+                rest = &rest[valid_len + invalid_len..];
+                if rest.is_empty() {
+                    break;
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Escapes every byte as regex source matching that exact byte: ASCII
+/// alphanumerics and `_` are passed through literally, other ASCII bytes
+/// become `\xHH`, and non-ASCII bytes (including invalid-UTF-8 ones) become
+/// `(?-u:\xHH)`, since in Unicode mode a bare `\xHH` above `\x7f` matches the
+/// UTF-8 encoding of U+00HH rather than the raw byte.
+fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        if b.is_ascii_alphanumeric() || b == b'_' {
+            out.push(b as char);
+        } else if b.is_ascii() {
+            out.push_str(&format!("\\x{:02x}", b));
+        } else {
+            out.push_str(&format!("(?-u:\\x{:02x})", b));
+        }
+    }
+    out
+}
+
+/// Byte-oriented counterpart to `translate_glob`.
+fn translate_glob_bytes(glob: &[u8]) -> String {
+    let mut out = String::new();
+    let mut literal_run = Vec::new();
+
+    let flush = |literal_run: &mut Vec<u8>, out: &mut String| {
+        if !literal_run.is_empty() {
+            out.push_str(&escape_bytes(literal_run));
+            literal_run.clear();
+        }
+    };
+
+    for &b in glob {
+        match b {
+            b'*' => {
+                flush(&mut literal_run, &mut out);
+                out.push_str(".*");
+            }
+            b'?' => {
+                flush(&mut literal_run, &mut out);
+                out.push('.');
+            }
+            _ => literal_run.push(b),
+        }
+    }
+    flush(&mut literal_run, &mut out);
+
+    out
+}
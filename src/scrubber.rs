@@ -0,0 +1,221 @@
+use pyo3::prelude::*;
+use pyo3::exceptions::PyIOError;
+use indexmap::IndexMap;
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+
+use crate::engine::Engine;
+
+/// A precompiled set of scrubbing rules, ready to be applied to text or files.
+///
+/// Compiling an `IndexMap<String, String>` of rules into regexes is the
+/// dominant cost for small inputs (e.g. sanitizing a single API request), since
+/// the free functions in this crate redo it on every call. `Scrubber` compiles
+/// once in `new` and keeps the result behind an `Arc`; the Rayon workers of a
+/// call all share that one compiled set rather than holding clones of their
+/// own.
+///
+/// Pass `combined=True` to match all rules in a single alternation regex
+/// instead of looping over each one; this trades a small compile-time cost
+/// for a much faster per-line scan when there are many rules. See
+/// [`crate::engine::Engine`] for the semantics this implies.
+///
+/// # Example
+///
+/// ```python
+/// import sentinel_rs
+///
+/// scrubber = sentinel_rs.Scrubber({r'\bpassword=\S+': 'password=[REDACTED]'})
+/// scrubber.scrub_text('password=hunter2')
+/// scrubber.scrub_file('input.log', 'output.log')
+///
+/// fast = sentinel_rs.Scrubber(many_rules, combined=True)
+/// ```
+#[pyclass]
+pub struct Scrubber {
+    engine: Engine,
+}
+
+#[pymethods]
+impl Scrubber {
+    #[new]
+    #[pyo3(signature = (rules, combined=false))]
+    pub(crate) fn new(rules: IndexMap<String, String>, combined: bool) -> PyResult<Self> {
+        Ok(Scrubber {
+            engine: Engine::compile(&rules, combined)?,
+        })
+    }
+
+    /// Transforms a single string using the precompiled rules.
+    pub(crate) fn scrub_text(&self, text: String) -> String {
+        self.engine.scrub(&text)
+    }
+
+    /// Processes a log file in parallel using the precompiled rules, writing
+    /// results to `output_path`. Mirrors `scrub_logs_parallel` but without
+    /// recompiling the rules.
+    pub(crate) fn scrub_file(&self, input_path: String, output_path: String) -> PyResult<usize> {
+        let engine = self.engine.cloned();
+
+        let input_file = File::open(&input_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
+
+        let reader = BufReader::new(input_file);
+        let lines: Vec<String> = reader
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PyIOError::new_err(format!("Failed to read input file: {}", e)))?;
+
+        let line_count = lines.len();
+
+        let scrubbed_lines: Vec<String> = lines
+            .par_iter()
+            .map(|line| engine.scrub(line))
+            .collect();
+
+        let output_file = File::create(&output_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
+
+        let mut writer = BufWriter::new(output_file);
+        for line in scrubbed_lines {
+            writeln!(writer, "{}", line)
+                .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
+        }
+
+        writer.flush()
+            .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
+
+        Ok(line_count)
+    }
+
+    /// Processes a large log file via memory-mapped I/O using the precompiled
+    /// rules. Mirrors `scrub_logs_mmap` but without recompiling the rules.
+    ///
+    /// Scrubs the mapping in fixed-size, line-aligned chunks rather than
+    /// collecting every line (and every scrubbed line) into memory at once —
+    /// see [`crate::mmap_chunks`] — so memory stays bounded regardless of
+    /// input size.
+    pub(crate) fn scrub_file_mmap(&self, input_path: String, output_path: String) -> PyResult<usize> {
+        let input_file = File::open(&input_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
+
+        let mmap = unsafe { memmap2::Mmap::map(&input_file) }
+            .map_err(|e| PyIOError::new_err(format!("Failed to memory-map input file: {}", e)))?;
+
+        let output_file = File::create(&output_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
+
+        let mut writer = BufWriter::new(output_file);
+        let line_count = crate::mmap_chunks::scrub_mmap_chunked(&self.engine, &mmap, &mut writer)?;
+
+        writer.flush()
+            .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
+
+        Ok(line_count)
+    }
+
+    /// Same as `scrub_text`, but also returns a per-rule match count —
+    /// original rule key to number of replacements — for compliance
+    /// reporting (GDPR/HIPAA/PCI redaction proof).
+    pub(crate) fn scrub_text_with_stats(&self, text: String) -> (String, IndexMap<String, usize>) {
+        let mut counts = vec![0usize; self.engine.rule_count()];
+        let scrubbed = self.engine.scrub_with_stats(&text, &mut counts);
+        (scrubbed, self.stats_dict(counts))
+    }
+
+    /// Same as `scrub_file`, but also returns a per-rule match count.
+    /// Counts are accumulated per Rayon fold and reduced across workers
+    /// alongside the scrubbed lines, so there's no second pass over the data.
+    pub(crate) fn scrub_file_with_stats(
+        &self,
+        input_path: String,
+        output_path: String,
+    ) -> PyResult<(usize, IndexMap<String, usize>)> {
+        let engine = self.engine.cloned();
+        let rule_count = engine.rule_count();
+
+        let input_file = File::open(&input_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
+
+        let reader = BufReader::new(input_file);
+        let lines: Vec<String> = reader
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PyIOError::new_err(format!("Failed to read input file: {}", e)))?;
+
+        let line_count = lines.len();
+
+        let (scrubbed_lines, counts): (Vec<String>, Vec<usize>) = lines
+            .par_iter()
+            .fold(
+                || (Vec::new(), vec![0usize; rule_count]),
+                |(mut out, mut counts), line| {
+                    out.push(engine.scrub_with_stats(line, &mut counts));
+                    (out, counts)
+                },
+            )
+            .reduce(
+                || (Vec::new(), vec![0usize; rule_count]),
+                |(mut out_a, mut counts_a), (out_b, counts_b)| {
+                    out_a.extend(out_b);
+                    for (a, b) in counts_a.iter_mut().zip(counts_b) {
+                        *a += b;
+                    }
+                    (out_a, counts_a)
+                },
+            );
+
+        let output_file = File::create(&output_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
+
+        let mut writer = BufWriter::new(output_file);
+        for line in scrubbed_lines {
+            writeln!(writer, "{}", line)
+                .map_err(|e| PyIOError::new_err(format!("Failed to write to output file: {}", e)))?;
+        }
+
+        writer.flush()
+            .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
+
+        Ok((line_count, self.stats_dict(counts)))
+    }
+
+    /// Same as `scrub_file_mmap`, but also returns a per-rule match count.
+    pub(crate) fn scrub_file_mmap_with_stats(
+        &self,
+        input_path: String,
+        output_path: String,
+    ) -> PyResult<(usize, IndexMap<String, usize>)> {
+        let input_file = File::open(&input_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to open input file '{}': {}", input_path, e)))?;
+
+        let mmap = unsafe { memmap2::Mmap::map(&input_file) }
+            .map_err(|e| PyIOError::new_err(format!("Failed to memory-map input file: {}", e)))?;
+
+        let output_file = File::create(&output_path)
+            .map_err(|e| PyIOError::new_err(format!("Failed to create output file '{}': {}", output_path, e)))?;
+
+        let mut writer = BufWriter::new(output_file);
+        let (line_count, counts) =
+            crate::mmap_chunks::scrub_mmap_chunked_with_stats(&self.engine, &mmap, &mut writer)?;
+
+        writer.flush()
+            .map_err(|e| PyIOError::new_err(format!("Failed to flush output file: {}", e)))?;
+
+        Ok((line_count, self.stats_dict(counts)))
+    }
+}
+
+impl Scrubber {
+    /// Pairs rule keys with their final counts, in rule-definition order, for
+    /// the `_with_stats` methods above.
+    fn stats_dict(&self, counts: Vec<usize>) -> IndexMap<String, usize> {
+        self.engine
+            .rule_keys()
+            .iter()
+            .cloned()
+            .zip(counts)
+            .collect()
+    }
+}